@@ -0,0 +1,55 @@
+use std::collections::Bound;
+use std::ops::{Range, RangeFrom, RangeFull, RangeInclusive, RangeTo};
+
+use crate::OneRange;
+
+impl<T> From<Range<T>> for OneRange<T> {
+    fn from(range: Range<T>) -> Self {
+        OneRange {
+            start: Bound::Included(range.start),
+            end: Bound::Excluded(range.end),
+            step: (),
+        }
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for OneRange<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (start, end) = range.into_inner();
+        OneRange {
+            start: Bound::Included(start),
+            end: Bound::Included(end),
+            step: (),
+        }
+    }
+}
+
+impl<T> From<RangeFrom<T>> for OneRange<T> {
+    fn from(range: RangeFrom<T>) -> Self {
+        OneRange {
+            start: Bound::Included(range.start),
+            end: Bound::Unbounded,
+            step: (),
+        }
+    }
+}
+
+impl<T> From<RangeTo<T>> for OneRange<T> {
+    fn from(range: RangeTo<T>) -> Self {
+        OneRange {
+            start: Bound::Unbounded,
+            end: Bound::Excluded(range.end),
+            step: (),
+        }
+    }
+}
+
+impl<T> From<RangeFull> for OneRange<T> {
+    fn from(_: RangeFull) -> Self {
+        OneRange {
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            step: (),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use std::collections::Bound;
+use std::ops::{Index, IndexMut, Range, RangeBounds};
+
+use crate::OneRange;
+
+/// `std::slice::SliceIndex` is sealed (it has a private `Sealed` supertrait),
+/// so it can't be implemented outside `std` for `OneRange`. `Index`/`IndexMut`
+/// are not sealed, so we implement those directly against `[T]` instead,
+/// which gives the same `&data[range!(2, =5)]` call-site ergonomics.
+///
+/// Only the unit-step form (`OneRange<usize>`) is indexable: a slice is
+/// always contiguous, and a stride can't be represented as a `&[T]`, so
+/// there's no `OneRange<usize, usize>` impl here.
+fn to_half_open(range: &OneRange<usize>, len: usize) -> Range<usize> {
+    let start = match range.start_bound() {
+        Bound::Included(&start) => start,
+        _ => panic!("slicing with an unbounded start is not supported"),
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&end) => {
+            assert!(
+                end < len,
+                "range end index {end} out of range for slice of length {len}"
+            );
+            end.checked_add(1).expect("range end index overflowed")
+        }
+        // As produced by `From<Range<usize>>`/`From<RangeTo<usize>>`: already
+        // half-open, so unlike the `Included` arm above there's no off-by-one
+        // to correct for.
+        Bound::Excluded(&end) => {
+            assert!(
+                end <= len,
+                "range end index {end} out of range for slice of length {len}"
+            );
+            end
+        }
+        Bound::Unbounded => panic!("slicing with an unbounded end is not supported"),
+    };
+    start..end
+}
+
+impl<T> Index<OneRange<usize>> for [T] {
+    type Output = [T];
+
+    fn index(&self, range: OneRange<usize>) -> &Self::Output {
+        &self[to_half_open(&range, self.len())]
+    }
+}
+
+impl<T> IndexMut<OneRange<usize>> for [T] {
+    fn index_mut(&mut self, range: OneRange<usize>) -> &mut Self::Output {
+        let bounds = to_half_open(&range, self.len());
+        &mut self[bounds]
+    }
+}
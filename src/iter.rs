@@ -0,0 +1,108 @@
+use num_traits::{CheckedAdd, CheckedSub, One};
+
+/// Resolves the direction and per-step magnitude a [`OneRangeIter`] walks in.
+///
+/// Direction always follows the bounds themselves (`start <= end` walks up,
+/// `start > end` walks down) so a reversed range like `range!(10, =0)` counts
+/// down without the caller having to say so explicitly. The step only
+/// supplies the magnitude: a unit step (`()`) moves by `T::one()`, and an
+/// explicit numeric step moves by its absolute value, so a negative step
+/// (e.g. `range!(10, =0, -2)`) is just another way to spell the magnitude.
+///
+/// The `StepValue<T> for T` side (used by the stepped `OneRange<T, T>` form)
+/// is implemented per concrete type alongside `into_iter!` in `lib.rs`,
+/// rather than as a blanket `impl<T> StepValue<T> for T` here — a blanket
+/// impl would conflict with the `()` impl below, since nothing stops a
+/// (nonsensical) `OneRange<(), ()>` from being named.
+pub(crate) trait StepValue<T> {
+    fn magnitude(&self) -> T;
+}
+
+impl<T: One> StepValue<T> for () {
+    fn magnitude(&self) -> T {
+        T::one()
+    }
+}
+
+/// Iterator produced by `OneRange::iter`, generic over any element type that
+/// supports checked addition/subtraction (`num_traits::CheckedAdd` /
+/// `CheckedSub`) rather than a fixed list of integer primitives.
+///
+/// `checked_add`/`checked_sub` stopping the iterator on overflow (instead of
+/// panicking or wrapping) is what lets an inclusive range ending at `T::MAX`
+/// (or counting down to `T::MIN`) terminate: the last in-range value is
+/// still yielded, and `done` is set so the next call returns `None` instead
+/// of overflowing.
+pub(crate) struct OneRangeIter<T> {
+    pub(crate) current: T,
+    pub(crate) end: T,
+    pub(crate) ascending: bool,
+    pub(crate) magnitude: T,
+    pub(crate) done: bool,
+}
+
+impl<T> Iterator for OneRangeIter<T>
+where
+    T: Copy + PartialOrd + CheckedAdd + CheckedSub,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let current = self.current;
+        let stepped = if self.ascending {
+            current.checked_add(&self.magnitude)
+        } else {
+            current.checked_sub(&self.magnitude)
+        };
+        let past_end = |next: T| {
+            if self.ascending {
+                next > self.end
+            } else {
+                next < self.end
+            }
+        };
+        match stepped {
+            Some(next) if !past_end(next) => self.current = next,
+            _ => self.done = true,
+        }
+        Some(current)
+    }
+}
+
+impl<T> DoubleEndedIterator for OneRangeIter<T>
+where
+    T: Copy + PartialOrd + CheckedAdd + CheckedSub,
+{
+    /// Consumes from the `end` side inward. Like `next`, this assumes the
+    /// two ends haven't already crossed. `end` is re-aligned to `current`'s
+    /// arithmetic sequence by `OneRange::iter` before this struct is built,
+    /// so a step that doesn't evenly divide the span still only ever yields
+    /// values `next` would also yield — interleaving `next` and `next_back`
+    /// just isn't guaranteed to stop exactly at the midpoint.
+    fn next_back(&mut self) -> Option<T> {
+        if self.done {
+            return None;
+        }
+        let current = self.end;
+        let stepped = if self.ascending {
+            self.end.checked_sub(&self.magnitude)
+        } else {
+            self.end.checked_add(&self.magnitude)
+        };
+        let past_start = |next: T| {
+            if self.ascending {
+                next < self.current
+            } else {
+                next > self.current
+            }
+        };
+        match stepped {
+            Some(next) if !past_start(next) => self.end = next,
+            _ => self.done = true,
+        }
+        Some(current)
+    }
+}
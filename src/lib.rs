@@ -1,40 +1,60 @@
+mod convert;
+mod index;
 mod iter;
 
 use std::cmp::Ordering;
 use std::collections::Bound;
-use std::ops::{RangeBounds};
+use std::ops::RangeBounds;
+
+use num_traits::{CheckedAdd, CheckedRem, CheckedSub, One, Zero};
+
+use iter::{OneRangeIter, StepValue};
 
 #[derive(Copy, Clone, Debug)]
 struct OneRange<T, Step = ()> {
-    start: T,
-    end: T,
+    start: Bound<T>,
+    end: Bound<T>,
     step: Step,
 }
 
-trait MinMax {
-    fn max(_: Self) -> Self;
-    fn min(_: Self) -> Self;
-}
-
+// Only exercised by this crate's own tests — there's no public constructor
+// for `OneRange` yet, so nothing outside `mod tests` can name it.
+#[cfg(test)]
 macro_rules! range {
+    (..) => {
+        OneRange {
+            start: Bound::Unbounded,
+            end: Bound::Unbounded,
+            step: (),
+        }
+    };
+
+    ($start: expr, ..) => {
+        OneRange {
+            start: Bound::Included($start),
+            end: Bound::Unbounded,
+            step: (),
+        }
+    };
+
     ($start: expr, =$end: expr, 1) => {
         OneRange {
-            start: $start,
-            end: $end,
+            start: Bound::Included($start),
+            end: Bound::Included($end),
             step: (),
         }
     };
 
     ($start: expr, =$end: expr, $step:expr) => {
         OneRange {
-            start: $start,
-            end: $end,
+            start: Bound::Included($start),
+            end: Bound::Included($end),
             step: $step,
         }
     };
 
     ($start: expr, =$end: expr) => {
-        range!($start, $end, 1)
+        range!($start, =$end, 1)
     };
 
     ($start :expr, $end: expr) => {
@@ -46,61 +66,70 @@ macro_rules! range {
     };
 
     (..$end: expr) => {
-        range!(MinMax::min($end), $end, 1)
+        OneRange {
+            start: Bound::Unbounded,
+            end: Bound::Included($end - 1),
+            step: (),
+        }
     };
 
     (..$end: expr, $step:expr) => {
-        range!(MinMax::min($end), $end, $step)
+        OneRange {
+            start: Bound::Unbounded,
+            end: Bound::Included($end - 1),
+            step: $step,
+        }
     };
 
     (..=$end: expr, $step:expr) => {
-        range!(MinMax::min($end), $end, $step)
+        OneRange {
+            start: Bound::Unbounded,
+            end: Bound::Included($end),
+            step: $step,
+        }
     };
 
     (..=$end: expr) => {
-        range!(MinMax::min($end), =$end, 1)
+        OneRange {
+            start: Bound::Unbounded,
+            end: Bound::Included($end),
+            step: (),
+        }
     };
 }
 
 macro_rules! into_iter {
     ($($t:ty),+) => {
         $(
-        impl OneRange<$t, ()> {
-            fn iter(&self) -> impl Iterator<Item=$t> {
-                self.start..self.end
-            }
-        }
-
-        impl OneRange<$t, usize> {
-            fn iter(&self) -> impl Iterator<Item=$t> {
-                (self.start..self.end).step_by(self.step)
-            }
-        }
-
         impl IntoIterator for OneRange<$t> {
             type Item=$t;
-            type IntoIter=std::ops::RangeInclusive<$t>;
+            type IntoIter=OneRangeIter<$t>;
 
             fn into_iter(self) -> Self::IntoIter {
-                self.start..=self.end
+                self.iter()
             }
         }
 
-        impl IntoIterator for OneRange<$t, usize> {
+        impl IntoIterator for OneRange<$t, $t> {
             type Item=$t;
-            type IntoIter=std::iter::StepBy<std::ops::RangeInclusive<$t>>;
+            type IntoIter=OneRangeIter<$t>;
 
             fn into_iter(self) -> Self::IntoIter {
-                (self.start..=self.end).step_by(self.step)
+                self.iter()
             }
         }
 
-        impl MinMax for $t {
-            fn max(_:Self) -> Self {
-                Self::MAX
-            }
-            fn min(_:Self) -> Self {
-                Self::MIN
+        impl StepValue<$t> for $t {
+            fn magnitude(&self) -> $t {
+                if *self >= <$t as Zero>::zero() {
+                    *self
+                } else {
+                    // `self` can be `$t::MIN`, whose true magnitude doesn't fit back
+                    // into `$t` (e.g. `-(i8::MIN)` is `128`, which overflows `i8`).
+                    // Saturate to `$t::MAX` rather than letting that wrap back around
+                    // to a negative "magnitude".
+                    <$t as Zero>::zero().checked_sub(*self).unwrap_or(<$t>::MAX)
+                }
             }
         }
         )+
@@ -109,31 +138,152 @@ macro_rules! into_iter {
 
 into_iter!(u8,u16,u32,u64,u128,i8,i16,i32,i64,i128);
 
-impl<T> RangeBounds<T> for OneRange<T> {
+impl<T, S> OneRange<T, S>
+where
+    T: Copy + PartialOrd + CheckedAdd + CheckedSub + CheckedRem + One + Zero,
+    S: StepValue<T> + Copy,
+{
+    /// Panics if the start isn't `Bound::Included`, or the end is
+    /// `Bound::Unbounded` — iteration needs a concrete cursor and a concrete
+    /// stopping point, so a `OneRange` built from `..` or `start..` (whose
+    /// end is itself unbounded) can't be iterated. Use it as a `RangeBounds`
+    /// argument instead.
+    ///
+    /// A `Bound::Excluded` end (as produced by `From<Range<T>>` /
+    /// `From<RangeTo<T>>`) is normalized to the last included value before
+    /// stepping begins, mirroring how `Range<T>`'s own `Iterator` handles its
+    /// exclusive end; a degenerate excluded end (`current >= end`) yields an
+    /// empty iterator rather than panicking, matching `Range`'s behavior for
+    /// `start >= end`.
+    fn iter(&self) -> OneRangeIter<T> {
+        let current = match self.start {
+            Bound::Included(start) => start,
+            _ => panic!("OneRange::iter requires a bounded start"),
+        };
+        let (end, done) = match self.end {
+            Bound::Included(end) => (end, false),
+            Bound::Excluded(end) if current < end => (
+                end.checked_sub(&T::one())
+                    .expect("excluded end has no predecessor"),
+                false,
+            ),
+            Bound::Excluded(_) => (current, true),
+            Bound::Unbounded => panic!("OneRange::iter requires a bounded end"),
+        };
+        let ascending = current <= end;
+        let magnitude = self.step.magnitude();
+        // Re-align `end` to the last value actually reachable from `current`
+        // by whole steps of `magnitude`, so `next_back` (which walks inward
+        // from `end`) stays on the same arithmetic sequence `next` walks
+        // outward from `current`, instead of landing on an off-grid value
+        // when the span isn't an exact multiple of the step.
+        let end = if done {
+            end
+        } else {
+            let (hi, lo) = if ascending { (end, current) } else { (current, end) };
+            // `hi - lo` only overflows for a signed span straddling zero; the
+            // two ends' own remainders then give the span's remainder instead.
+            let remainder = match hi.checked_sub(&lo) {
+                Some(distance) => distance.checked_rem(&magnitude),
+                None => match (hi.checked_rem(&magnitude), lo.checked_rem(&magnitude)) {
+                    (Some(h), Some(l)) => match magnitude.checked_add(&l) {
+                        Some(m_l) if h >= m_l => h.checked_sub(&m_l),
+                        _ => h.checked_sub(&l),
+                    },
+                    _ => None,
+                },
+            };
+            match remainder {
+                Some(remainder) if !remainder.is_zero() => {
+                    if ascending {
+                        end.checked_sub(&remainder).unwrap_or(end)
+                    } else {
+                        end.checked_add(&remainder).unwrap_or(end)
+                    }
+                }
+                _ => end,
+            }
+        };
+        OneRangeIter {
+            current,
+            end,
+            ascending,
+            magnitude,
+            done,
+        }
+    }
+}
+
+impl<T, S> RangeBounds<T> for OneRange<T, S> {
     fn start_bound(&self) -> Bound<&T> {
-        Bound::Included(&self.start)
+        match &self.start {
+            Bound::Included(start) => Bound::Included(start),
+            Bound::Excluded(start) => Bound::Excluded(start),
+            Bound::Unbounded => Bound::Unbounded,
+        }
     }
 
     fn end_bound(&self) -> Bound<&T> {
-        Bound::Included(&self.end)
+        match &self.end {
+            Bound::Included(end) => Bound::Included(end),
+            Bound::Excluded(end) => Bound::Excluded(end),
+            Bound::Unbounded => Bound::Unbounded,
+        }
     }
 
     fn contains<U>(&self, item: &U) -> bool where T: PartialOrd<U>, U: ?Sized + PartialOrd<T> {
-        matches!((self.start.partial_cmp(item), item.partial_cmp(&self.end)), (Some(Ordering::Equal | Ordering::Less), Some(Ordering::Equal | Ordering::Less)))
+        let after_start = match &self.start {
+            Bound::Included(start) => matches!(start.partial_cmp(item), Some(Ordering::Equal | Ordering::Less)),
+            Bound::Excluded(start) => matches!(start.partial_cmp(item), Some(Ordering::Less)),
+            Bound::Unbounded => true,
+        };
+        let before_end = match &self.end {
+            Bound::Included(end) => matches!(item.partial_cmp(end), Some(Ordering::Equal | Ordering::Less)),
+            Bound::Excluded(end) => matches!(item.partial_cmp(end), Some(Ordering::Less)),
+            Bound::Unbounded => true,
+        };
+        after_start && before_end
     }
 }
 
-impl<T> RangeBounds<T> for OneRange<T, usize> {
+impl<T, S> RangeBounds<T> for &OneRange<T, S> {
     fn start_bound(&self) -> Bound<&T> {
-        Bound::Included(&self.start)
+        (**self).start_bound()
     }
 
     fn end_bound(&self) -> Bound<&T> {
-        Bound::Included(&self.end)
+        (**self).end_bound()
     }
 
     fn contains<U>(&self, item: &U) -> bool where T: PartialOrd<U>, U: ?Sized + PartialOrd<T> {
-        matches!((self.start.partial_cmp(item), item.partial_cmp(&self.end)), (Some(Ordering::Equal | Ordering::Less), Some(Ordering::Equal | Ordering::Less)))
+        (**self).contains(item)
+    }
+}
+
+/// Mirrors std's `impl<T> RangeBounds<T> for Range<&T>` (and friends): a
+/// `OneRange` holding references can be checked against owned values
+/// directly, so it can be passed to `BTreeMap::range` without cloning `T`.
+impl<T, S> RangeBounds<T> for OneRange<&T, S> {
+    fn start_bound(&self) -> Bound<&T> {
+        self.start
+    }
+
+    fn end_bound(&self) -> Bound<&T> {
+        self.end
+    }
+
+    fn contains<U>(&self, item: &U) -> bool where T: PartialOrd<U>, U: ?Sized + PartialOrd<T> {
+        let after_start = match self.start {
+            Bound::Included(start) => matches!(start.partial_cmp(item), Some(Ordering::Equal | Ordering::Less)),
+            Bound::Excluded(start) => matches!(start.partial_cmp(item), Some(Ordering::Less)),
+            Bound::Unbounded => true,
+        };
+        let before_end = match self.end {
+            Bound::Included(end) => matches!(item.partial_cmp(end), Some(Ordering::Equal | Ordering::Less)),
+            Bound::Excluded(end) => matches!(item.partial_cmp(end), Some(Ordering::Less)),
+            Bound::Unbounded => true,
+        };
+        after_start && before_end
     }
 }
 
@@ -157,7 +307,167 @@ mod tests {
     fn range_open() {
         let r = range!(..123);
         assert!(r.contains(&3));
+        assert_eq!(r.start_bound(), Bound::Unbounded);
+
         let r = range!(..=5u8);
+        assert_eq!(r.end_bound(), Bound::Included(&5));
+    }
+
+    #[test]
+    fn range_fully_unbounded() {
+        let r: OneRange<u8> = range!(..);
+        assert_eq!(r.start_bound(), Bound::Unbounded);
+        assert_eq!(r.end_bound(), Bound::Unbounded);
+        assert!(r.contains(&200));
+    }
+
+    #[test]
+    fn range_from() {
+        let r = range!(5u8, ..);
+        assert_eq!(r.start_bound(), Bound::Included(&5));
+        assert_eq!(r.end_bound(), Bound::Unbounded);
+        assert!(r.contains(&200));
+        assert!(!r.contains(&3));
+    }
+
+    #[test]
+    fn range_inclusive_iterates() {
+        let r = range!(0, =5u8);
         assert_eq!(r.into_iter().collect::<Vec<_>>(), [0, 1, 2, 3, 4, 5]);
     }
+
+    #[test]
+    fn range_descending() {
+        let r = range!(5, =0u8);
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), [5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn range_rev_matches_descending() {
+        let r = range!(0, =5u8);
+        assert_eq!(r.into_iter().rev().collect::<Vec<_>>(), [5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn range_negative_step() {
+        let r = range!(10, =0i32, -2);
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), [10, 8, 6, 4, 2, 0]);
+    }
+
+    #[test]
+    fn range_step_min_saturates() {
+        // `i8::MIN`'s magnitude (128) doesn't fit in `i8`; it should saturate
+        // to `i8::MAX` rather than silently stay negative.
+        let r = range!(0i8, =10i8, i8::MIN);
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), [0]);
+    }
+
+    #[test]
+    fn range_rev_with_uneven_step_stays_on_grid() {
+        // 10 isn't a multiple of the step 3, so the forward sequence stops
+        // at 9; `rev()` must walk that same grid rather than starting from
+        // the raw end bound.
+        let r = range!(0, =10u8, 3);
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), [0, 3, 6, 9]);
+        assert_eq!(r.into_iter().rev().collect::<Vec<_>>(), [9, 6, 3, 0]);
+    }
+
+    #[test]
+    fn slice_inclusive_range() {
+        let data = [10, 20, 30, 40, 50];
+        assert_eq!(&data[range!(1, =3)], &[20, 30, 40]);
+    }
+
+    #[test]
+    fn slice_inclusive_range_mut() {
+        let mut data = [10, 20, 30, 40, 50];
+        data[range!(1, =3)].copy_from_slice(&[1, 2, 3]);
+        assert_eq!(data, [10, 1, 2, 3, 50]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn slice_inclusive_range_out_of_bounds_panics() {
+        let data = [10, 20, 30];
+        let _ = &data[range!(0, =5)];
+    }
+
+    #[test]
+    fn slice_with_converted_std_range() {
+        let data = [10, 20, 30, 40, 50];
+        let r: OneRange<usize> = (1usize..4usize).into();
+        assert_eq!(&data[r], &[20, 30, 40]);
+
+        // The excluded end sits exactly at the slice's length.
+        let r: OneRange<usize> = (2usize..5usize).into();
+        assert_eq!(&data[r], &[30, 40, 50]);
+    }
+
+    #[test]
+    fn from_std_range() {
+        let r: OneRange<u8> = (1..5).into();
+        assert_eq!(r.start_bound(), Bound::Included(&1));
+        assert_eq!(r.end_bound(), Bound::Excluded(&5));
+    }
+
+    #[test]
+    fn from_std_range_iterates() {
+        let r: OneRange<u8> = (1u8..5u8).into();
+        assert_eq!(r.into_iter().collect::<Vec<_>>(), [1, 2, 3, 4]);
+
+        let empty: OneRange<u8> = (5u8..5u8).into();
+        assert_eq!(empty.into_iter().collect::<Vec<_>>(), []);
+    }
+
+    #[test]
+    fn from_std_range_inclusive() {
+        let r: OneRange<u8> = (1..=5).into();
+        assert_eq!(r.start_bound(), Bound::Included(&1));
+        assert_eq!(r.end_bound(), Bound::Included(&5));
+    }
+
+    #[test]
+    fn from_std_range_from() {
+        let r: OneRange<u8> = (1..).into();
+        assert_eq!(r.start_bound(), Bound::Included(&1));
+        assert_eq!(r.end_bound(), Bound::Unbounded);
+    }
+
+    #[test]
+    fn from_std_range_to() {
+        let r: OneRange<u8> = (..5).into();
+        assert_eq!(r.start_bound(), Bound::Unbounded);
+        assert_eq!(r.end_bound(), Bound::Excluded(&5));
+    }
+
+    #[test]
+    fn from_std_range_full() {
+        let r: OneRange<u8> = (..).into();
+        assert_eq!(r.start_bound(), Bound::Unbounded);
+        assert_eq!(r.end_bound(), Bound::Unbounded);
+    }
+
+    fn contains_via_range_bounds(bounds: impl RangeBounds<u8>, item: u8) -> bool {
+        bounds.contains(&item)
+    }
+
+    #[test]
+    #[allow(clippy::needless_borrows_for_generic_args)] // exercises `&OneRange: RangeBounds`, not `OneRange: RangeBounds`
+    fn range_bounds_for_reference() {
+        let r = range!(1, =5u8);
+        assert!(contains_via_range_bounds(&r, 3));
+        assert!(!contains_via_range_bounds(&r, 9));
+    }
+
+    #[test]
+    fn range_bounds_for_range_of_references() {
+        let (start, end) = (1u8, 5u8);
+        let r = OneRange {
+            start: Bound::Included(&start),
+            end: Bound::Included(&end),
+            step: (),
+        };
+        assert!(<OneRange<&u8> as RangeBounds<u8>>::contains(&r, &3));
+        assert!(!<OneRange<&u8> as RangeBounds<u8>>::contains(&r, &9));
+    }
 }